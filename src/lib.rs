@@ -0,0 +1,4 @@
+pub mod modules;
+pub mod repl;
+pub mod translate;
+pub mod utils;
@@ -1,8 +1,11 @@
 use heraclitus_compiler::prelude::*;
-use crate::{utils::{metadata::ParserMetadata, TranslateMetadata}, translate::compute::{translate_computation, ArithOp}};
+use crate::{utils::{metadata::ParserMetadata, TranslateMetadata}, translate::compute::{translate_computation, translate_float_computation, format_float, ArithOp}};
 use super::{super::expr::Expr, parse_left_expr, expression_arms_of_type};
+#[cfg(test)]
+use super::matches_expected;
 use crate::modules::{Type, Typed};
 use crate::translate::module::TranslateModule;
+use crate::translate::const_eval::{ConstFold, ConstNum, fold_div};
 
 #[derive(Debug)]
 pub struct Div {
@@ -12,7 +15,11 @@ pub struct Div {
 
 impl Typed for Div {
     fn get_type(&self) -> Type {
-        Type::Num
+        match (self.left.get_type(), self.right.get_type()) {
+            (Type::Float, _) | (_, Type::Float) => Type::Float,
+            // Anything else is rejected by `expression_arms_of_type` in `parse`
+            _ => Type::Int
+        }
     }
 }
 
@@ -32,15 +39,55 @@ impl SyntaxModule<ParserMetadata> for Div {
         token(meta, "/")?;
         syntax(meta, &mut *self.right)?;
         let error = "Divide operation can only divide numbers";
-        expression_arms_of_type(meta, &self.left, &self.right, Type::Num, tok, error);
+        expression_arms_of_type(meta, &self.left, &self.right, Type::Float, tok.clone(), error)?;
+        // Literal division by zero is a compile error rather than something
+        // left to blow up at runtime
+        if let (Some(left), Some(right)) = (self.left.fold(), self.right.fold()) {
+            if fold_div(left, right).is_err() {
+                return error!(meta, tok, "Division by zero");
+            }
+        }
         Ok(())
     }
 }
 
+impl ConstFold for Div {
+    fn fold(&self) -> Option<ConstNum> {
+        let left = self.left.fold()?;
+        let right = self.right.fold()?;
+        fold_div(left, right).ok()
+    }
+}
+
 impl TranslateModule for Div {
     fn translate(&self, meta: &mut TranslateMetadata) -> String {
+        // Both operands are known at compile time - fold to a single constant
+        // instead of bloating the generated bash with runtime arithmetic
+        if let Some(result) = self.fold() {
+            return match result {
+                ConstNum::Int(value) => value.to_string(),
+                // Formatted the same way `translate_float_computation` formats
+                // a runtime `awk` result, so folding doesn't change output
+                ConstNum::Float(value) => format_float(value)
+            };
+        }
         let left = self.left.translate(meta);
         let right = self.right.translate(meta);
-        translate_computation(meta, ArithOp::Div, Some(left), Some(right))
+        match self.get_type() {
+            Type::Float => translate_float_computation(meta, ArithOp::Div, Some(left), Some(right)),
+            _ => translate_computation(meta, ArithOp::Div, Some(left), Some(right))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn float_expectation_accepts_int_and_float_operands() {
+        assert!(matches_expected(&Type::Float, &Type::Int));
+        assert!(matches_expected(&Type::Float, &Type::Float));
+        assert!(!matches_expected(&Type::Float, &Type::Text));
     }
 }
\ No newline at end of file
@@ -0,0 +1,27 @@
+pub mod div;
+
+use heraclitus_compiler::prelude::*;
+use crate::utils::metadata::ParserMetadata;
+use crate::modules::{Type, Typed};
+use super::expr::Expr;
+
+pub fn parse_left_expr(meta: &mut ParserMetadata, expr: &mut Expr, _op: &str) -> SyntaxResult {
+    syntax(meta, expr)
+}
+
+// `Int` and `Float` belong to the same numeric family and coerce to one
+// another, so an `expected` of either one accepts both
+pub(crate) fn matches_expected(expected: &Type, kind: &Type) -> bool {
+    if expected.is_numeric() && kind.is_numeric() {
+        return true;
+    }
+    expected == kind
+}
+
+pub fn expression_arms_of_type(meta: &mut ParserMetadata, left: &Expr, right: &Expr, expected: Type, tok: Option<Token>, error: &str) -> SyntaxResult {
+    let matches = matches_expected(&expected, &left.get_type()) && matches_expected(&expected, &right.get_type());
+    if !matches {
+        return error!(meta, tok, error);
+    }
+    Ok(())
+}
@@ -0,0 +1,119 @@
+use heraclitus_compiler::prelude::*;
+use crate::utils::{metadata::ParserMetadata, TranslateMetadata};
+use crate::modules::{Type, Typed};
+use crate::modules::expression::expr::Expr;
+use crate::modules::expression::binop::matches_expected;
+use crate::translate::module::TranslateModule;
+
+// Invokes a value of type `Type::FunPtr`, e.g. `callback(1, 2)` where
+// `callback` holds a captured function reference rather than a direct call
+#[derive(Debug)]
+pub struct FunPtrCall {
+    var_name: String,
+    args: Vec<Expr>,
+    returns: Type
+}
+
+impl Typed for FunPtrCall {
+    fn get_type(&self) -> Type {
+        self.returns.clone()
+    }
+}
+
+impl SyntaxModule<ParserMetadata> for FunPtrCall {
+    syntax_name!("FunPtrCall");
+
+    fn new() -> Self {
+        FunPtrCall {
+            var_name: String::new(),
+            args: vec![],
+            returns: Type::Null
+        }
+    }
+
+    fn parse(&mut self, meta: &mut ParserMetadata) -> SyntaxResult {
+        let tok = meta.get_current_token();
+        let name = variable(meta, variable_name_extensions())?;
+        let var = match meta.mem.get_variable(&name) {
+            Some(var) => var,
+            None => {
+                let message = match meta.mem.suggest_variable(&name) {
+                    Some(suggestion) => format!("Variable '{name}' not found, did you mean '{suggestion}'?"),
+                    None => format!("Variable '{name}' not found")
+                };
+                return error!(meta, tok, message);
+            }
+        };
+        let Type::FunPtr(_, arg_types, returns) = var.kind.clone() else {
+            return error!(meta, tok, format!("Variable '{name}' is not a function reference"));
+        };
+        token(meta, "(")?;
+        let mut args = vec![];
+        if !matches!(meta.get_current_token(), Some(Token { word, .. }) if word == ")") {
+            loop {
+                let mut arg = Expr::new();
+                syntax(meta, &mut arg)?;
+                args.push(arg);
+                if token(meta, ",").is_err() {
+                    break;
+                }
+            }
+        }
+        token(meta, ")")?;
+        if args.len() != arg_types.len() {
+            return error!(meta, tok, format!("Function reference '{name}' expects {} arguments, got {}", arg_types.len(), args.len()));
+        }
+        // Arity alone doesn't catch a value of the wrong type flowing into the
+        // captured signature, so check each argument against it too
+        for (index, (arg, expected)) in args.iter().zip(arg_types.iter()).enumerate() {
+            if !matches_expected(expected, &arg.get_type()) {
+                return error!(meta, tok, format!("Argument {} to function reference '{name}' has the wrong type", index + 1));
+            }
+        }
+        self.var_name = name;
+        self.args = args;
+        self.returns = *returns;
+        Ok(())
+    }
+}
+
+impl TranslateModule for FunPtrCall {
+    fn translate(&self, meta: &mut TranslateMetadata) -> String {
+        let args = self.args.iter()
+            .map(|arg| arg.translate(meta))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let tag = format!("${{{}}}", self.var_name);
+        let targets = meta.mem.get_fun_ptr_targets(&self.var_name);
+        // Each distinct captured target monomorphizes to its own generated
+        // bash function - dispatch on the tag to call the right one
+        let arms = targets.iter()
+            .map(|id| format!("{id}) __fun_{id} {args} ;;"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        format!("$(case {tag} in\n{arms}\nesac)")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::memory::Memory;
+
+    #[test]
+    fn dispatches_to_every_target_recorded_for_the_variable() {
+        let mut mem = Memory::new();
+        mem.push_scope();
+        mem.record_fun_ptr_target("callback", 3);
+        mem.record_fun_ptr_target("callback", 7);
+        let mut meta = TranslateMetadata { mem };
+        let call = FunPtrCall {
+            var_name: "callback".to_string(),
+            args: vec![],
+            returns: Type::Null
+        };
+        let code = call.translate(&mut meta);
+        assert!(code.contains("3) __fun_3  ;;"));
+        assert!(code.contains("7) __fun_7  ;;"));
+    }
+}
@@ -0,0 +1,65 @@
+use heraclitus_compiler::prelude::*;
+use crate::utils::{metadata::ParserMetadata, TranslateMetadata};
+use crate::modules::{Type, Typed};
+use crate::translate::module::TranslateModule;
+
+// Captures a declared function by name into a value, e.g. `&map_double`, so it
+// can be passed around and invoked indirectly through a `FunPtrCall`
+#[derive(Debug)]
+pub struct FunRef {
+    name: String,
+    id: usize,
+    args: Vec<Type>,
+    returns: Type
+}
+
+impl Typed for FunRef {
+    fn get_type(&self) -> Type {
+        Type::FunPtr(self.id, self.args.clone(), Box::new(self.returns.clone()))
+    }
+}
+
+impl SyntaxModule<ParserMetadata> for FunRef {
+    syntax_name!("FunRef");
+
+    fn new() -> Self {
+        FunRef {
+            name: String::new(),
+            id: 0,
+            args: vec![],
+            returns: Type::Null
+        }
+    }
+
+    fn parse(&mut self, meta: &mut ParserMetadata) -> SyntaxResult {
+        token(meta, "&")?;
+        let tok = meta.get_current_token();
+        let name = variable(meta, variable_name_extensions())?;
+        // Resolved at capture time, so the right `FunctionMap` instance (and
+        // the pointer's signature for type-checking the indirect call) is
+        // already known by the time this value is used
+        match meta.mem.capture_function(&name) {
+            Some(Type::FunPtr(id, args, returns)) => {
+                self.id = id;
+                self.args = args;
+                self.returns = *returns;
+            }
+            _ => {
+                let message = match meta.mem.suggest_function(&name) {
+                    Some(suggestion) => format!("Function '{name}' not found, did you mean '{suggestion}'?"),
+                    None => format!("Function '{name}' not found")
+                };
+                return error!(meta, tok, message);
+            }
+        }
+        self.name = name;
+        Ok(())
+    }
+}
+
+impl TranslateModule for FunRef {
+    fn translate(&self, _meta: &mut TranslateMetadata) -> String {
+        // Lowers to the small integer tag the call site dispatches on
+        self.id.to_string()
+    }
+}
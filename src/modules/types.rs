@@ -0,0 +1,28 @@
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Type {
+    Int,
+    Float,
+    Bool,
+    Text,
+    Null,
+    Array(Box<Type>),
+    Generic,
+    Failable(Box<Type>),
+    // A captured function reference: the declaration id lowering dispatches
+    // on, followed by its signature - e.g. `fn(Int, Int) -> Int`
+    FunPtr(usize, Vec<Type>, Box<Type>),
+    // Fresh unification variable produced while inferring a generic function's
+    // body - never appears in a fully resolved, user-facing type
+    Var(usize)
+}
+
+impl Type {
+    // Numbers that can be used interchangeably in arithmetic - `Int` widens to `Float`
+    pub fn is_numeric(&self) -> bool {
+        matches!(self, Type::Int | Type::Float)
+    }
+}
+
+pub trait Typed {
+    fn get_type(&self) -> Type;
+}
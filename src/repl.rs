@@ -0,0 +1,77 @@
+use std::io::{self, Write};
+use crate::compiler::AmberCompiler;
+use crate::utils::memory::Memory;
+
+// Keeps one long-lived `Memory` across the whole session, so a variable or
+// function declared on one line stays visible to every line after it instead
+// of the program being re-parsed from scratch on each input
+pub struct Repl {
+    mem: Memory,
+    script: String
+}
+
+impl Repl {
+    pub fn new() -> Repl {
+        let mut mem = Memory::new();
+        // The REPL only ever has a single, persistent global frame
+        mem.push_scope();
+        Repl {
+            mem,
+            script: String::new()
+        }
+    }
+
+    // Parses and translates just the entered fragment, appends the generated
+    // bash to the running script, and carries `variable_id` forward on
+    // `self.mem` so global IDs never collide across lines
+    pub fn eval_line(&mut self, line: &str) -> Result<String, String> {
+        let snapshot = self.mem.clone();
+        match AmberCompiler::new(line.to_string()).compile(self.mem.clone()) {
+            Ok((code, mem)) => {
+                self.mem = mem;
+                self.script.push_str(&code);
+                self.script.push('\n');
+                Ok(code)
+            }
+            Err(err) => {
+                // A typo shouldn't corrupt the session - roll back to the
+                // state from before the failed fragment was entered
+                self.mem = snapshot;
+                Err(err.to_string())
+            }
+        }
+    }
+
+    pub fn available_variables(&self) -> Vec<String> {
+        self.mem.get_available_variables().into_iter().cloned().collect()
+    }
+
+    pub fn available_functions(&self) -> Vec<String> {
+        self.mem.get_available_functions().into_iter().cloned().collect()
+    }
+
+    pub fn script(&self) -> &str {
+        &self.script
+    }
+}
+
+pub fn run() {
+    let mut repl = Repl::new();
+    let stdin = io::stdin();
+    loop {
+        print!("amber> ");
+        io::stdout().flush().ok();
+        let mut line = String::new();
+        if stdin.read_line(&mut line).unwrap_or(0) == 0 {
+            break;
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            continue;
+        }
+        match repl.eval_line(line) {
+            Ok(code) => println!("{code}"),
+            Err(err) => eprintln!("error: {err}")
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use crate::utils::metadata::TranslateMetadata;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ArithOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Modulo
+}
+
+impl ArithOp {
+    fn as_bash_op(&self) -> &'static str {
+        match self {
+            ArithOp::Add => "+",
+            ArithOp::Sub => "-",
+            ArithOp::Mul => "*",
+            ArithOp::Div => "/",
+            ArithOp::Modulo => "%"
+        }
+    }
+
+    fn as_awk_op(&self) -> &'static str {
+        // Modulo is not meaningful for floats in Amber, so it's only ever reached for Add/Sub/Mul/Div
+        self.as_bash_op()
+    }
+}
+
+// Bash's `$(( ))` only performs truncating integer division, so this path is only
+// ever used once both operands have been checked to be `Type::Int`
+pub fn translate_computation(_meta: &mut TranslateMetadata, op: ArithOp, left: Option<String>, right: Option<String>) -> String {
+    match (left, right) {
+        (Some(left), Some(right)) => format!("$(( {left} {} {right} ))", op.as_bash_op()),
+        (Some(left), None) => format!("$(( {} {left} ))", op.as_bash_op()),
+        _ => unreachable!("translate_computation called without any operands")
+    }
+}
+
+// Any operation involving a `Type::Float` operand is routed through `awk` since
+// bash has no native floating-point arithmetic
+pub fn translate_float_computation(_meta: &mut TranslateMetadata, op: ArithOp, left: Option<String>, right: Option<String>) -> String {
+    match (left, right) {
+        (Some(left), Some(right)) => format!("$(awk \"BEGIN {{ print {left} {} {right} }}\")", op.as_awk_op()),
+        _ => unreachable!("translate_float_computation called without both operands")
+    }
+}
+
+const FORMAT_FLOAT_SIG_FIGS: i32 = 6;
+
+// Mirrors awk's default `%.6g` `OFMT` (6 significant figures, trailing zeros
+// trimmed) so a constant-folded `Float` renders identically to the same
+// computation run through `translate_float_computation` at runtime. Like
+// `%g`, switches to scientific notation once the exponent falls outside
+// `[-4, SIG_FIGS)` rather than ever printing in fixed notation unconditionally
+pub fn format_float(value: f64) -> String {
+    if value == 0.0 {
+        return "0".to_string();
+    }
+    // Like `%g`, the exponent is the one of the value already rounded to
+    // `SIG_FIGS` (999999.7 rounds to 1.00000e6 and so goes scientific)
+    let precision = (FORMAT_FLOAT_SIG_FIGS - 1).max(0) as usize;
+    let exponent: i32 = format!("{value:.precision$e}")
+        .rsplit('e').next().and_then(|exp| exp.parse().ok()).unwrap_or(0);
+    if exponent < -4 || exponent >= FORMAT_FLOAT_SIG_FIGS {
+        format_float_scientific(value, exponent)
+    } else {
+        let decimals = (FORMAT_FLOAT_SIG_FIGS - 1 - exponent).max(0) as usize;
+        trim_trailing_zeros(&format!("{value:.decimals$}"))
+    }
+}
+
+fn format_float_scientific(value: f64, mut exponent: i32) -> String {
+    let decimals = (FORMAT_FLOAT_SIG_FIGS - 1).max(0) as usize;
+    let mut mantissa = format!("{:.decimals$}", value / 10f64.powi(exponent));
+    // Rounding the mantissa to `decimals` places can carry it up to +/-10.0,
+    // which belongs to the next exponent (e.g. 9.999996 -> "10.0000")
+    if mantissa.trim_start_matches('-').starts_with("10") {
+        exponent += 1;
+        mantissa = format!("{:.decimals$}", value / 10f64.powi(exponent));
+    }
+    let sign = if exponent < 0 { '-' } else { '+' };
+    format!("{}e{sign}{:02}", trim_trailing_zeros(&mantissa), exponent.abs())
+}
+
+fn trim_trailing_zeros(formatted: &str) -> String {
+    // Zeros before any decimal point are significant (e.g. "120000")
+    if !formatted.contains('.') {
+        return formatted.to_string();
+    }
+    let trimmed = formatted.trim_end_matches('0').trim_end_matches('.');
+    if trimmed.is_empty() || trimmed == "-" {
+        "0".to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_float_matches_awk_six_sig_figs() {
+        assert_eq!(format_float(10.0 / 3.0), "3.33333");
+        assert_eq!(format_float(2.5), "2.5");
+        assert_eq!(format_float(0.0), "0");
+    }
+
+    #[test]
+    fn format_float_switches_to_scientific_outside_fixed_range() {
+        assert_eq!(format_float(1.0 / 300000.0), "3.33333e-06");
+        assert_eq!(format_float(1234567.891), "1.23457e+06");
+    }
+}
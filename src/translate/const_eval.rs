@@ -0,0 +1,57 @@
+#[derive(Clone, Copy, Debug)]
+pub enum ConstNum {
+    Int(i64),
+    Float(f64)
+}
+
+impl ConstNum {
+    pub fn as_f64(self) -> f64 {
+        match self {
+            ConstNum::Int(v) => v as f64,
+            ConstNum::Float(v) => v
+        }
+    }
+}
+
+// Implemented by literal nodes and, so far, by `Div` - so nested literal
+// division (e.g. `(2 / 3) / 2`) collapses to a single constant before any
+// bash is produced. Returns `None` as soon as either side isn't known at
+// compile time. Add/Sub/Mul/Modulo don't have modules in this series yet;
+// implement this for them too once they do
+pub trait ConstFold {
+    fn fold(&self) -> Option<ConstNum>;
+}
+
+// Mirrors `Div::get_type` - `Int / Int` stays `Int`, anything else widens to
+// `Float`. A literal zero divisor is a compile error rather than a runtime one
+pub fn fold_div(left: ConstNum, right: ConstNum) -> Result<ConstNum, String> {
+    match (left, right) {
+        (ConstNum::Int(_), ConstNum::Int(0)) => Err("Division by zero".to_string()),
+        (ConstNum::Int(l), ConstNum::Int(r)) => Ok(ConstNum::Int(l / r)),
+        (_, right) if right.as_f64() == 0.0 => Err("Division by zero".to_string()),
+        (left, right) => Ok(ConstNum::Float(left.as_f64() / right.as_f64()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn int_division_truncates() {
+        let result = fold_div(ConstNum::Int(5), ConstNum::Int(2)).unwrap();
+        assert!(matches!(result, ConstNum::Int(2)));
+    }
+
+    #[test]
+    fn float_operand_widens_result() {
+        let result = fold_div(ConstNum::Float(5.0), ConstNum::Int(2)).unwrap();
+        assert!(matches!(result, ConstNum::Float(v) if v == 2.5));
+    }
+
+    #[test]
+    fn literal_division_by_zero_is_rejected() {
+        assert!(fold_div(ConstNum::Int(1), ConstNum::Int(0)).is_err());
+        assert!(fold_div(ConstNum::Float(1.0), ConstNum::Float(0.0)).is_err());
+    }
+}
@@ -0,0 +1,3 @@
+pub mod compute;
+pub mod const_eval;
+pub mod module;
@@ -0,0 +1,5 @@
+use crate::utils::metadata::TranslateMetadata;
+
+pub trait TranslateModule {
+    fn translate(&self, meta: &mut TranslateMetadata) -> String;
+}
@@ -0,0 +1,135 @@
+use std::collections::HashMap;
+use crate::modules::types::Type;
+
+// Substitution built up while unifying the constraints collected from a
+// generic function body - maps a fresh `Type::Var` to the type it was solved to
+pub type Substitution = HashMap<usize, Type>;
+
+// A generalized, universally quantified function signature. `vars` lists the
+// type variables that are free to be instantiated differently at each call site
+#[derive(Clone, Debug)]
+pub struct Scheme {
+    pub vars: Vec<usize>,
+    pub args: Vec<Type>,
+    pub returns: Type
+}
+
+pub struct TypeVarGen {
+    next: usize
+}
+
+impl TypeVarGen {
+    pub fn new() -> TypeVarGen {
+        TypeVarGen { next: 0 }
+    }
+
+    pub fn fresh(&mut self) -> Type {
+        let var = Type::Var(self.next);
+        self.next += 1;
+        var
+    }
+}
+
+// Follows the substitution chain until it reaches a concrete type (or an
+// unresolved variable)
+pub fn apply(sub: &Substitution, kind: &Type) -> Type {
+    match kind {
+        Type::Var(id) => match sub.get(id) {
+            Some(resolved) => apply(sub, resolved),
+            None => kind.clone()
+        },
+        Type::Array(inner) => Type::Array(Box::new(apply(sub, inner))),
+        Type::Failable(inner) => Type::Failable(Box::new(apply(sub, inner))),
+        other => other.clone()
+    }
+}
+
+fn occurs(id: usize, kind: &Type) -> bool {
+    match kind {
+        Type::Var(other) => *other == id,
+        Type::Array(inner) | Type::Failable(inner) => occurs(id, inner),
+        _ => false
+    }
+}
+
+// Standard Robinson unification: bind a variable in the substitution map if
+// either side is one (after an occurs-check to reject e.g. `T = List<T>`),
+// otherwise recurse structurally and error on a genuine mismatch
+pub fn unify(sub: &mut Substitution, left: &Type, right: &Type) -> Result<(), String> {
+    let left = apply(sub, left);
+    let right = apply(sub, right);
+    match (&left, &right) {
+        (Type::Var(a), Type::Var(b)) if a == b => Ok(()),
+        (Type::Var(id), _) => {
+            if occurs(*id, &right) {
+                return Err(format!("occurs check failed: T{id} occurs in {right:?}"));
+            }
+            sub.insert(*id, right);
+            Ok(())
+        }
+        (_, Type::Var(id)) => {
+            if occurs(*id, &left) {
+                return Err(format!("occurs check failed: T{id} occurs in {left:?}"));
+            }
+            sub.insert(*id, left);
+            Ok(())
+        }
+        (Type::Array(a), Type::Array(b)) => unify(sub, a, b),
+        (Type::Failable(a), Type::Failable(b)) => unify(sub, a, b),
+        // `Int` widens to `Float`, so the two unify without adding a substitution
+        (Type::Int, Type::Float) | (Type::Float, Type::Int) => Ok(()),
+        _ if left == right => Ok(()),
+        _ => Err(format!("type mismatch: expected {left:?}, found {right:?}"))
+    }
+}
+
+// Instantiates a generalized scheme against the concrete argument types found
+// at a call site, returning the substitution (so callers can deduplicate
+// instances whose inferred signatures coincide) and the resolved return type
+pub fn instantiate(scheme: &Scheme, call_args: &[Type]) -> Result<(Substitution, Type), String> {
+    let mut sub = Substitution::new();
+    for (expected, given) in scheme.args.iter().zip(call_args) {
+        unify(&mut sub, expected, given)?;
+    }
+    let returns = apply(&sub, &scheme.returns);
+    Ok((sub, returns))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unify_binds_a_variable_to_a_concrete_type() {
+        let mut sub = Substitution::new();
+        unify(&mut sub, &Type::Var(0), &Type::Text).unwrap();
+        assert_eq!(apply(&sub, &Type::Var(0)), Type::Text);
+    }
+
+    #[test]
+    fn unify_widens_int_and_float() {
+        let mut sub = Substitution::new();
+        assert!(unify(&mut sub, &Type::Int, &Type::Float).is_ok());
+    }
+
+    #[test]
+    fn unify_rejects_occurs_check() {
+        let mut sub = Substitution::new();
+        let result = unify(&mut sub, &Type::Var(0), &Type::Array(Box::new(Type::Var(0))));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn unify_rejects_genuine_mismatch() {
+        let mut sub = Substitution::new();
+        assert!(unify(&mut sub, &Type::Text, &Type::Bool).is_err());
+    }
+
+    #[test]
+    fn instantiate_resolves_returns_from_argument() {
+        // `fn identity(x: T) -> T` called with a `Text` argument
+        let scheme = Scheme { vars: vec![0], args: vec![Type::Var(0)], returns: Type::Var(0) };
+        let (_, returns) = instantiate(&scheme, &[Type::Text]).unwrap();
+        assert_eq!(returns, Type::Text);
+    }
+}
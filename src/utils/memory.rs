@@ -1,7 +1,7 @@
 use heraclitus_compiler::prelude::*;
 use std::collections::{HashMap, BTreeSet};
 use crate::modules::{types::Type, block::Block, function::declaration_utils::FunctionDeclSyntax};
-use super::{function_map::{FunctionMap, FunctionInstance}, exports::Exports, ParserMetadata};
+use super::{function_map::{FunctionMap, FunctionInstance}, exports::Exports, infer::{Scheme, TypeVarGen, instantiate}, ParserMetadata};
 
 #[derive(Clone, Debug)]
 pub struct FunctionDecl {
@@ -12,7 +12,11 @@ pub struct FunctionDecl {
     pub meta: ParserMetadata,
     pub typed: bool,
     pub is_public: bool,
-    pub id: usize
+    pub id: usize,
+    // Generalized signature for a generic declaration - `None` once every
+    // parameter and the return type are concrete, since there's then nothing
+    // left to instantiate
+    pub scheme: Option<Scheme>
 }
 
 #[derive(Clone, Debug)]
@@ -25,14 +29,44 @@ pub struct VariableDecl {
 #[derive(Clone, Debug)]
 pub struct ScopeUnit {
     pub vars: HashMap<String, VariableDecl>,
-    pub funs: HashMap<String, FunctionDecl>
+    pub funs: HashMap<String, FunctionDecl>,
+    // Distinct function ids captured into each first-class `FunPtr` binding in
+    // this scope, keyed by variable name - used to emit its dispatch table.
+    // Scoped like `vars` rather than kept flat on `Memory`, so two unrelated
+    // variables that happen to share a name in different scopes don't bleed
+    // into the same dispatch list
+    pub fun_ptr_targets: HashMap<String, Vec<usize>>
+}
+
+// Minimum number of single-character edits (insertions, deletions,
+// substitutions) needed to turn `a` into `b`
+fn levenshtein(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut dp = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in dp.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for j in 0..=b.len() {
+        dp[0][j] = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            dp[i][j] = (dp[i - 1][j] + 1)
+                .min(dp[i][j - 1] + 1)
+                .min(dp[i - 1][j - 1] + cost);
+        }
+    }
+    dp[a.len()][b.len()]
 }
 
 impl ScopeUnit {
     fn new() -> ScopeUnit {
         ScopeUnit {
             vars: HashMap::new(),
-            funs: HashMap::new()
+            funs: HashMap::new(),
+            fun_ptr_targets: HashMap::new()
         }
     }
 }
@@ -74,6 +108,12 @@ impl Memory {
             global_id = Some(self.variable_id);
             self.variable_id += 1;
         }
+        // Binding a captured function reference records its target, so an
+        // indirect call through this variable knows which generated bash
+        // function to dispatch to
+        if let Type::FunPtr(id, ..) = &kind {
+            self.record_fun_ptr_target(name, *id);
+        }
         let scope = self.scopes.last_mut().unwrap();
         scope.vars.insert(name.to_string(), VariableDecl {
             name: name.to_string(),
@@ -111,8 +151,37 @@ impl Memory {
         res.is_none()
     }
 
+    // Builds the universally quantified signature for a generic declaration.
+    // `Type::Generic` doesn't carry an identity of its own, so every occurrence
+    // in one declaration - each parameter and the return type alike - shares a
+    // single fresh variable; that's what lets a call site's unification alone
+    // resolve `fn identity(x: Generic) -> Generic` without ever walking the
+    // body. The tradeoff is `fn pick(a: Generic, b: Generic)` wrongly requires
+    // `a` and `b` to be the same concrete type at every call site. Returns
+    // `None` once the signature is already fully concrete - there's nothing
+    // left to infer
+    fn generalize_signature(args: &[(String, Type)], returns: &Type) -> Option<Scheme> {
+        if !args.iter().any(|(_, kind)| kind == &Type::Generic) && returns != &Type::Generic {
+            return None;
+        }
+        let var = TypeVarGen::new().fresh();
+        let args: Vec<Type> = args.iter()
+            .map(|(_, kind)| if kind == &Type::Generic { var.clone() } else { kind.clone() })
+            .collect();
+        let returns = if returns == &Type::Generic { var.clone() } else { returns.clone() };
+        let vars = match var {
+            Type::Var(id) => vec![id],
+            _ => unreachable!("TypeVarGen::fresh always produces a Type::Var")
+        };
+        Some(Scheme { vars, args, returns })
+    }
+
     pub fn add_function_declaration(&mut self, meta: ParserMetadata, decl: FunctionDeclSyntax) -> Option<usize> {
-        let typed = !decl.args.iter().any(|(_, kind)| kind == &Type::Generic);
+        let scheme = Memory::generalize_signature(&decl.args, &decl.returns);
+        // A declaration whose signature still holds `Generic` is marked untyped.
+        // Only its signature is generalized into `scheme`; its body is not
+        // walked or checked against the fresh type variable here
+        let typed = scheme.is_none();
         let scope = self.scopes.last_mut().unwrap();
         // Add function declaration to the function map
         let id = self.function_map.add_declaration();
@@ -126,6 +195,7 @@ impl Memory {
             meta,
             typed,
             id,
+            scheme,
         };
         // Add function declaration to the scope
         let success = scope.funs.insert(decl.name, function_declaration.clone());
@@ -141,7 +211,25 @@ impl Memory {
         }
     }
 
+    // Instantiates a generic declaration's scheme against the concrete argument
+    // types found at a call site, unifying each parameter and resolving the
+    // return type through the substitution. Only the resolved return type is
+    // returned; the substitution it was solved with is discarded and not kept
+    // on the `FunctionInstance`
+    pub fn instantiate_function(&self, decl: &FunctionDecl, call_args: &[Type]) -> Result<Type, String> {
+        match &decl.scheme {
+            Some(scheme) => instantiate(scheme, call_args).map(|(_, returns)| returns),
+            None => Ok(decl.returns.clone())
+        }
+    }
+
     pub fn add_function_instance(&mut self, id: usize, args: &[Type], returns: Type, body: Block) -> usize {
+        // If the declaration is generic, instantiate its scheme against this
+        // call site's argument types so the stored instance carries the
+        // inferred return type rather than the declaration's unresolved one
+        let returns = self.find_function_decl(id)
+            .and_then(|decl| self.instantiate_function(decl, args).ok())
+            .unwrap_or(returns);
         self.function_map.add_instance(id, FunctionInstance {
             args: args.to_vec(),
             returns,
@@ -149,6 +237,12 @@ impl Memory {
         })
     }
 
+    fn find_function_decl(&self, id: usize) -> Option<&FunctionDecl> {
+        self.scopes.iter()
+            .flat_map(|scope| scope.funs.values())
+            .find(|decl| decl.id == id)
+    }
+
     pub fn get_function(&self, name: &str) -> Option<&FunctionDecl> {
         for scope in self.scopes.iter().rev() {
             if let Some(fun) = scope.funs.get(name) {
@@ -162,10 +256,6 @@ impl Memory {
         self.function_map.get(id)
     }
 
-    pub fn set_function_map(&mut self, old_meta: &ParserMetadata) {
-        self.function_map = old_meta.mem.function_map.clone();
-    }
-
     pub fn get_available_functions(&self) -> BTreeSet<&String> {
         let mut set = BTreeSet::new();
         for scope in self.scopes.iter().rev() {
@@ -175,4 +265,98 @@ impl Memory {
         }
         set
     }
+
+    // Closest name an undefined identifier could be a typo of - used to turn a
+    // silent "not found" into a "did you mean '...'?" diagnostic
+    const SUGGESTION_MAX_DISTANCE: usize = 3;
+
+    fn suggest_name<'a>(name: &str, candidates: impl Iterator<Item = &'a str>) -> Option<String> {
+        let mut best: Option<(usize, &str)> = None;
+        // Scopes are walked innermost-first by the caller, so among equally
+        // close candidates the first one found wins - i.e. the nearest scope
+        for candidate in candidates {
+            let distance = levenshtein(name, candidate);
+            if distance == 0 || distance > Memory::SUGGESTION_MAX_DISTANCE {
+                continue;
+            }
+            if best.map_or(true, |(best_distance, _)| distance < best_distance) {
+                best = Some((distance, candidate));
+            }
+        }
+        best.map(|(_, candidate)| candidate.to_string())
+    }
+
+    // Suggests the closest in-scope variable name to `name`, for use when
+    // `get_variable` returns `None`
+    pub fn suggest_variable(&self, name: &str) -> Option<String> {
+        let candidates = self.scopes.iter().rev()
+            .flat_map(|scope| scope.vars.keys().map(String::as_str));
+        Memory::suggest_name(name, candidates)
+    }
+
+    // Suggests the closest in-scope function name to `name`, for use when
+    // `get_function` returns `None`
+    pub fn suggest_function(&self, name: &str) -> Option<String> {
+        let candidates = self.scopes.iter().rev()
+            .flat_map(|scope| scope.funs.keys().map(String::as_str));
+        Memory::suggest_name(name, candidates)
+    }
+
+    // Resolves a function name captured as a first-class value to the
+    // `Type::FunPtr` used to type-check the indirect call site - carrying the
+    // declaration id lowering later dispatches on
+    pub fn capture_function(&self, name: &str) -> Option<Type> {
+        let decl = self.get_function(name)?;
+        let args = decl.args.iter().map(|(_, kind)| kind.clone()).collect();
+        Some(Type::FunPtr(decl.id, args, Box::new(decl.returns.clone())))
+    }
+
+    // Records that `var` may hold the function captured as `id`, so the call
+    // site can monomorphize each distinct target into its existing generated
+    // bash function and dispatch via the tag. Recorded against the current
+    // (innermost) scope, the same one `var` is about to be bound in
+    pub fn record_fun_ptr_target(&mut self, var: &str, id: usize) {
+        let scope = self.scopes.last_mut().unwrap();
+        let targets = scope.fun_ptr_targets.entry(var.to_string()).or_default();
+        if !targets.contains(&id) {
+            targets.push(id);
+        }
+    }
+
+    // Looked up the same way `get_variable` resolves `var` - innermost scope
+    // first - so a call site only ever sees the targets of the binding it
+    // actually resolves to, not a same-named `FunPtr` from another scope
+    pub fn get_fun_ptr_targets(&self, var: &str) -> &[usize] {
+        for scope in self.scopes.iter().rev() {
+            if let Some(targets) = scope.fun_ptr_targets.get(var) {
+                return targets;
+            }
+        }
+        &[]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn binding_a_fun_ptr_variable_records_its_target() {
+        let mut mem = Memory::new();
+        mem.push_scope();
+        mem.add_variable("callback", Type::FunPtr(3, vec![], Box::new(Type::Null)), false);
+        assert_eq!(mem.get_fun_ptr_targets("callback"), &[3]);
+    }
+
+    #[test]
+    fn same_named_fun_ptr_variables_in_different_scopes_dont_collide() {
+        let mut mem = Memory::new();
+        mem.push_scope();
+        mem.add_variable("callback", Type::FunPtr(3, vec![], Box::new(Type::Null)), false);
+        mem.push_scope();
+        mem.add_variable("callback", Type::FunPtr(7, vec![], Box::new(Type::Null)), false);
+        assert_eq!(mem.get_fun_ptr_targets("callback"), &[7]);
+        mem.pop_scope();
+        assert_eq!(mem.get_fun_ptr_targets("callback"), &[3]);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,11 @@
+use crate::utils::memory::Memory;
+
+#[derive(Clone, Debug)]
+pub struct ParserMetadata {
+    pub mem: Memory
+}
+
+#[derive(Clone, Debug)]
+pub struct TranslateMetadata {
+    pub mem: Memory
+}
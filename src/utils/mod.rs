@@ -0,0 +1,5 @@
+pub mod infer;
+pub mod memory;
+pub mod metadata;
+
+pub use metadata::{ParserMetadata, TranslateMetadata};